@@ -1,3 +1,7 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+
 use syn;
 
 /// Represent the `derivative` attributes on the input type (`struct`/`enum`).
@@ -13,8 +17,16 @@ pub struct Input {
     pub default: Option<InputDefault>,
     /// Whether `Eq` is present and its specitif attributes.
     pub eq: Option<InputEq>,
-    /// Whether `Eq` is present and its specitif attributes.
-    pub partial_eq: Option<InputPartialEq>,
+    /// Whether `Hash` is present and its specific attributes.
+    pub hash: Option<InputHash>,
+    /// Whether `Ord` is present and its specific attributes.
+    pub ord: Option<InputOrd>,
+    /// The `derivative(PartialEq(…))` attributes present, one per `impl PartialEq<Rhs>` to
+    /// generate. A plain entry (no `rhs`) compares against `Self`; an entry with `rhs` set
+    /// compares against that type instead, and both may coexist on the same item.
+    pub partial_eq: Vec<InputPartialEq>,
+    /// Whether `PartialOrd` is present and its specific attributes.
+    pub partial_ord: Option<InputPartialOrd>,
 }
 
 #[derive(Debug, Default)]
@@ -30,8 +42,14 @@ pub struct Field {
     default: FieldDefault,
     /// The parameters for `Eq`.
     eq_bound: Option<Vec<syn::WherePredicate>>,
+    /// The parameters for `Hash`.
+    hash: FieldHash,
+    /// The parameters for `Ord`.
+    ord_bound: Option<Vec<syn::WherePredicate>>,
     /// The parameters for `Eq`.
     partial_eq: FieldPartialEq,
+    /// The parameters for `PartialOrd`.
+    partial_ord: FieldPartialOrd,
 }
 
 #[derive(Debug, Default)]
@@ -55,6 +73,9 @@ pub struct InputCopy {
 pub struct InputDebug {
     /// The `bound` attribute if present and the corresponding bounds.
     bounds: Option<Vec<syn::WherePredicate>>,
+    /// The `rename_all` attribute if present: the casing convention to rename every field/variant
+    /// to in the generated `Debug` output.
+    rename_all: Option<RenameRule>,
     /// Whether the type is marked `transparent`.
     pub transparent: bool,
 }
@@ -76,12 +97,53 @@ pub struct InputEq {
 }
 
 #[derive(Debug, Default)]
-/// Represent the `derivative(PartialEq(…))` attributes on an input.
+/// Represent the `derivative(Hash(…))` attributes on an input.
+pub struct InputHash {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Ord(…))` attributes on an input.
+pub struct InputOrd {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// Allow `derivative(Ord)` on enums:
+    on_enum: bool,
+}
+
+#[derive(Debug, Default)]
+/// Represent a single `derivative(PartialEq(…))` attribute block on an input.
 pub struct InputPartialEq {
     /// The `bound` attribute if present and the corresponding bounds.
     bounds: Option<Vec<syn::WherePredicate>>,
     /// Allow `derivative(PartialEq)` on enums:
     on_enum: bool,
+    /// The `rhs` attribute if present: the type to compare `Self` against instead of `Self`.
+    rhs: Option<syn::Ty>,
+}
+
+impl InputPartialEq {
+    pub fn bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.bounds.as_ref().map(Vec::as_slice)
+    }
+
+    pub fn on_enum(&self) -> bool {
+        self.on_enum
+    }
+
+    pub fn rhs(&self) -> Option<&syn::Ty> {
+        self.rhs.as_ref()
+    }
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(PartialOrd(…))` attributes on an input.
+pub struct InputPartialOrd {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// Allow `derivative(PartialOrd)` on enums:
+    on_enum: bool,
 }
 
 #[derive(Debug, Default)]
@@ -100,6 +162,9 @@ pub struct FieldDebug {
     format_with: Option<syn::Path>,
     /// Whether the field is to be ignored from output.
     ignore: bool,
+    /// The `rename` attribute if present: the name to show instead of the field's own, taking
+    /// precedence over the container's `rename_all`.
+    rename: Option<String>,
 }
 
 #[derive(Debug, Default)]
@@ -111,6 +176,17 @@ pub struct FieldDefault {
     pub value: Option<syn::Expr>,
 }
 
+#[derive(Debug, Default)]
+/// Represents the `derivarive(Hash(…))` attributes on a field.
+pub struct FieldHash {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// The `hash_with` attribute if present and the path to the hashing function.
+    hash_with: Option<syn::Path>,
+    /// Whether the field is to be ignored when hashing.
+    ignore: bool,
+}
+
 #[derive(Debug, Default)]
 /// Represent the `derivarive(PartialEq(…))` attributes on a field.
 pub struct FieldPartialEq {
@@ -122,14 +198,46 @@ pub struct FieldPartialEq {
     ignore: bool,
 }
 
+#[derive(Debug, Default)]
+/// Represents the `derivarive(PartialOrd(…))` attributes on a field.
+pub struct FieldPartialOrd {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// The `compare_with` attribute if present and the path to the comparison function.
+    compare_with: Option<syn::Path>,
+    /// Whether the field is to be ignored when comparing.
+    ignore: bool,
+}
+
 macro_rules! for_all_attr {
-    (for ($name:ident, $value:ident) in $attrs:expr; $($body:tt)*) => {
+    (for ($cx:expr, $name:ident, $value:ident, $loc:ident) in $attrs:expr; $($body:tt)*) => {
+        let mut seen = HashSet::new();
+
         for meta_items in $attrs.iter().filter_map(derivative_attribute) {
-            for metaitem in meta_items.iter().map(read_items) {
-                let MetaItem($name, $value) = try!(metaitem);
+            for item in meta_items {
+                let $loc = ItemName::new(item_name(item));
+
+                let MetaItem($name, $value) = match read_items(&$cx, &$loc, item) {
+                    Some(meta_item) => meta_item,
+                    None => continue,
+                };
+
+                // `PartialEq` is allowed to repeat only when each block targets a distinct
+                // `rhs`: a plain entry (no `rhs`) would generate the same `impl
+                // PartialEq<Self>` as any other plain entry, so those still conflict.
+                let rhs = if $name == "PartialEq" {
+                    $value.iter().find(|&&(key, _)| key == "rhs").map(|&(_, rhs)| rhs)
+                } else {
+                    None
+                };
+                if !seen.insert(($name, rhs)) {
+                    $cx.error(&$loc, format!("duplicate `{}` entry", $name));
+                    continue;
+                }
+
                 match $name {
                     $($body)*
-                    _ => return Err(format!("unknown trait `{}`", $name)),
+                    _ => $cx.error(&$loc, format!("unknown trait `{}`", $name)),
                 }
             }
         }
@@ -137,22 +245,22 @@ macro_rules! for_all_attr {
 }
 
 macro_rules! match_attributes {
-    (let Some($name:ident) = $unwraped:expr; for $value:ident in $values:expr; $($body:tt)* ) => {
+    (let Some($name:ident) = $unwraped:expr; for ($cx:expr, $loc:expr, $value:ident) in $values:expr; $($body:tt)* ) => {
         let mut $name = $unwraped.take().unwrap_or_default();
 
         match_attributes! {
-            for $value in $values;
+            for ($cx, $loc, $value) in $values;
             $($body)*
         }
 
         $unwraped = Some($name);
     };
 
-    (for $value:ident in $values:expr; $($body:tt)* ) => {
+    (for ($cx:expr, $loc:expr, $value:ident) in $values:expr; $($body:tt)* ) => {
         for (name, $value) in $values {
             match name {
                 $($body)*
-                _ => return Err(format!("unknown attribute `{}`", name)),
+                _ => $cx.error($loc, format!("unknown attribute `{}`", name)),
             }
         }
     };
@@ -161,66 +269,121 @@ macro_rules! match_attributes {
 impl Input {
     /// Parse the `derivative` attributes on a type.
     pub fn from_ast(attrs: &[syn::Attribute]) -> Result<Input, String> {
+        let cx = Ctxt::new();
         let mut input = Input::default();
 
         for_all_attr! {
-            for (name, values) in attrs;
+            for (cx, name, values, loc) in attrs;
             "Clone" => {
                 match_attributes! {
                     let Some(clone) = input.clone;
-                    for value in values;
-                    "bound" => try!(parse_bound(&mut clone.bounds, value)),
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut clone.bounds, value),
                     "clone_from" => {
-                        clone.clone_from = try!(parse_boolean_meta_item(&value, true, "clone_from"));
+                        if let Some(v) = parse_boolean_meta_item(&cx, &loc, &value, true, "clone_from") {
+                            clone.clone_from = v;
+                        }
                     }
                 }
             }
             "Copy" => {
                 match_attributes! {
                     let Some(copy) = input.copy;
-                    for value in values;
-                    "bound" => try!(parse_bound(&mut copy.bounds, value)),
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut copy.bounds, value),
                 }
             }
             "Debug" => {
                 match_attributes! {
                     let Some(debug) = input.debug;
-                    for value in values;
-                    "bound" => try!(parse_bound(&mut debug.bounds, value)),
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut debug.bounds, value),
                     "transparent" => {
-                        debug.transparent = try!(parse_boolean_meta_item(&value, true, "transparent"));
+                        if let Some(v) = parse_boolean_meta_item(&cx, &loc, &value, true, "transparent") {
+                            debug.transparent = v;
+                        }
+                    }
+                    "rename_all" => {
+                        if let Some(rule) = parse_rename_rule(&cx, &loc, value) {
+                            debug.rename_all = Some(rule);
+                        }
                     }
                 }
             }
             "Default" => {
                 match_attributes! {
                     let Some(default) = input.default;
-                    for value in values;
-                    "bound" => try!(parse_bound(&mut default.bounds, value)),
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut default.bounds, value),
                     "new" => {
-                        default.new = try!(parse_boolean_meta_item(&value, true, "new"));
+                        if let Some(v) = parse_boolean_meta_item(&cx, &loc, &value, true, "new") {
+                            default.new = v;
+                        }
                     }
                 }
             }
             "Eq" => {
                 match_attributes! {
                     let Some(eq) = input.eq;
-                    for value in values;
-                    "bound" => try!(parse_bound(&mut eq.bounds, value)),
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut eq.bounds, value),
+                }
+            }
+            "Hash" => {
+                match_attributes! {
+                    let Some(hash) = input.hash;
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut hash.bounds, value),
+                }
+            }
+            "Ord" => {
+                match_attributes! {
+                    let Some(ord) = input.ord;
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut ord.bounds, value),
+                    "feature_allow_slow_enum" => {
+                        if let Some(v) = parse_boolean_meta_item(&cx, &loc, &value, true, "feature_allow_slow_enum") {
+                            ord.on_enum = v;
+                        }
+                    }
                 }
             }
             "PartialEq" => {
+                let mut partial_eq = InputPartialEq::default();
+
+                match_attributes! {
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut partial_eq.bounds, value),
+                    "feature_allow_slow_enum" => {
+                        if let Some(v) = parse_boolean_meta_item(&cx, &loc, &value, true, "feature_allow_slow_enum") {
+                            partial_eq.on_enum = v;
+                        }
+                    }
+                    "rhs" => {
+                        if let Some(ty) = parse_type_value(&cx, &loc, value, "rhs") {
+                            partial_eq.rhs = Some(ty);
+                        }
+                    }
+                }
+
+                input.partial_eq.push(partial_eq);
+            }
+            "PartialOrd" => {
                 match_attributes! {
-                    let Some(partial_eq) = input.partial_eq;
-                    for value in values;
-                    "bound" => try!(parse_bound(&mut partial_eq.bounds, value)),
+                    let Some(partial_ord) = input.partial_ord;
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut partial_ord.bounds, value),
                     "feature_allow_slow_enum" => {
-                        partial_eq.on_enum = try!(parse_boolean_meta_item(&value, true, "feature_allow_slow_enum"));
+                        if let Some(v) = parse_boolean_meta_item(&cx, &loc, &value, true, "feature_allow_slow_enum") {
+                            partial_ord.on_enum = v;
+                        }
                     }
                 }
             }
         }
 
+        try!(cx.check());
+
         Ok(input)
     }
 
@@ -240,6 +403,10 @@ impl Input {
         self.debug.as_ref().map_or(false, |d| d.transparent)
     }
 
+    pub fn debug_rename_all(&self) -> Option<RenameRule> {
+        self.debug.as_ref().and_then(|d| d.rename_all)
+    }
+
     pub fn default_bound(&self) -> Option<&[syn::WherePredicate]> {
         self.default.as_ref().map_or(None, |d| d.bounds.as_ref().map(Vec::as_slice))
     }
@@ -248,66 +415,137 @@ impl Input {
         self.eq.as_ref().map_or(None, |d| d.bounds.as_ref().map(Vec::as_slice))
     }
 
-    pub fn partial_eq_bound(&self) -> Option<&[syn::WherePredicate]> {
-        self.partial_eq.as_ref().map_or(None, |d| d.bounds.as_ref().map(Vec::as_slice))
+    pub fn hash_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.hash.as_ref().map_or(None, |d| d.bounds.as_ref().map(Vec::as_slice))
+    }
+
+    pub fn ord_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.ord.as_ref().map_or(None, |d| d.bounds.as_ref().map(Vec::as_slice))
+    }
+
+    pub fn ord_on_enum(&self) -> bool {
+        self.ord.as_ref().map_or(false, |d| d.on_enum)
+    }
+
+    /// The `derivative(PartialEq(…))` blocks present on this input, one per `impl
+    /// PartialEq<Rhs>` to generate.
+    pub fn partial_eqs(&self) -> &[InputPartialEq] {
+        &self.partial_eq
+    }
+
+    pub fn partial_ord_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.partial_ord.as_ref().map_or(None, |d| d.bounds.as_ref().map(Vec::as_slice))
     }
 
-    pub fn partial_eq_on_enum(&self) -> bool {
-        self.partial_eq.as_ref().map_or(false, |d| d.on_enum)
+    pub fn partial_ord_on_enum(&self) -> bool {
+        self.partial_ord.as_ref().map_or(false, |d| d.on_enum)
     }
 }
 
 impl Field {
     /// Parse the `derivative` attributes on a type.
     pub fn from_ast(field: &syn::Field) -> Result<Field, String> {
+        let cx = Ctxt::new();
         let mut out = Field::default();
 
         for_all_attr! {
-            for (name, values) in field.attrs;
+            for (cx, name, values, loc) in field.attrs;
             "Debug" => {
                 match_attributes! {
-                    for value in values;
-                    "bound" => try!(parse_bound(&mut out.debug.bounds, value)),
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut out.debug.bounds, value),
                     "format_with" => {
-                        let path = try!(value.ok_or_else(|| "`format_with` needs a value".to_string()));
-                        out.debug.format_with = Some(try!(syn::parse_path(path)));
+                        if let Some(path) = parse_path_value(&cx, &loc, value, "format_with") {
+                            out.debug.format_with = Some(path);
+                        }
                     }
                     "ignore" => {
-                        out.debug.ignore = try!(parse_boolean_meta_item(&value, true, "ignore"));
+                        if let Some(v) = parse_boolean_meta_item(&cx, &loc, &value, true, "ignore") {
+                            out.debug.ignore = v;
+                        }
+                    }
+                    "rename" => {
+                        if let Some(name) = parse_string_value(&cx, &loc, value, "rename") {
+                            out.debug.rename = Some(name);
+                        }
                     }
                 }
             }
             "Default" => {
                 match_attributes! {
-                    for value in values;
-                    "bound" => try!(parse_bound(&mut out.default.bounds, value)),
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut out.default.bounds, value),
                     "value" => {
-                        let value = try!(value.ok_or_else(|| "`value` needs a value".to_string()));
-                        out.default.value = Some(try!(syn::parse_expr(value)));
+                        if let Some(expr) = parse_expr_value(&cx, &loc, value, "value") {
+                            out.default.value = Some(expr);
+                        }
                     }
                 }
             }
             "Eq" => {
                 match_attributes! {
-                    for value in values;
-                    "bound" => try!(parse_bound(&mut out.eq_bound, value)),
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut out.eq_bound, value),
+                }
+            }
+            "Hash" => {
+                match_attributes! {
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut out.hash.bounds, value),
+                    "hash_with" => {
+                        if let Some(path) = parse_path_value(&cx, &loc, value, "hash_with") {
+                            out.hash.hash_with = Some(path);
+                        }
+                    }
+                    "ignore" => {
+                        if let Some(v) = parse_boolean_meta_item(&cx, &loc, &value, true, "ignore") {
+                            out.hash.ignore = v;
+                        }
+                    }
+                }
+            }
+            "Ord" => {
+                match_attributes! {
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut out.ord_bound, value),
                 }
             }
             "PartialEq" => {
                 match_attributes! {
-                    for value in values;
-                    "bound" => try!(parse_bound(&mut out.partial_eq.bounds, value)),
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut out.partial_eq.bounds, value),
                     "compare_with" => {
-                        let path = try!(value.ok_or_else(|| "`compare_with` needs a value".to_string()));
-                        out.partial_eq.compare_with = Some(try!(syn::parse_path(path)));
+                        if let Some(path) = parse_path_value(&cx, &loc, value, "compare_with") {
+                            out.partial_eq.compare_with = Some(path);
+                        }
                     }
                     "ignore" => {
-                        out.partial_eq.ignore = try!(parse_boolean_meta_item(&value, true, "ignore"));
+                        if let Some(v) = parse_boolean_meta_item(&cx, &loc, &value, true, "ignore") {
+                            out.partial_eq.ignore = v;
+                        }
+                    }
+                }
+            }
+            "PartialOrd" => {
+                match_attributes! {
+                    for (cx, &loc, value) in values;
+                    "bound" => parse_bound(&cx, &loc, &mut out.partial_ord.bounds, value),
+                    "compare_with" => {
+                        if let Some(path) = parse_path_value(&cx, &loc, value, "compare_with") {
+                            out.partial_ord.compare_with = Some(path);
+                        }
+                    }
+                    "ignore" => {
+                        if let Some(v) = parse_boolean_meta_item(&cx, &loc, &value, true, "ignore") {
+                            out.partial_ord.ignore = v;
+                        }
                     }
                 }
             }
         }
 
+        try!(cx.check());
+
         Ok(out)
     }
 
@@ -327,6 +565,10 @@ impl Field {
         self.debug.format_with.as_ref()
     }
 
+    pub fn debug_rename(&self) -> Option<&str> {
+        self.debug.rename.as_ref().map(String::as_str)
+    }
+
     pub fn ignore_debug(&self) -> bool {
         self.debug.ignore
     }
@@ -343,6 +585,22 @@ impl Field {
         self.eq_bound.as_ref().map(Vec::as_slice)
     }
 
+    pub fn hash_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.hash.bounds.as_ref().map(Vec::as_slice)
+    }
+
+    pub fn hash_with(&self) -> Option<&syn::Path> {
+        self.hash.hash_with.as_ref()
+    }
+
+    pub fn ignore_hash(&self) -> bool {
+        self.hash.ignore
+    }
+
+    pub fn ord_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.ord_bound.as_ref().map(Vec::as_slice)
+    }
+
     pub fn partial_eq_bound(&self) -> Option<&[syn::WherePredicate]> {
         self.partial_eq.bounds.as_ref().map(Vec::as_slice)
     }
@@ -354,6 +612,18 @@ impl Field {
     pub fn ignore_partial_eq(&self) -> bool {
         self.partial_eq.ignore
     }
+
+    pub fn partial_ord_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.partial_ord.bounds.as_ref().map(Vec::as_slice)
+    }
+
+    pub fn partial_ord_compare_with(&self) -> Option<&syn::Path> {
+        self.partial_ord.compare_with.as_ref()
+    }
+
+    pub fn ignore_partial_ord(&self) -> bool {
+        self.partial_ord.ignore
+    }
 }
 
 /// Represent an attribute.
@@ -365,35 +635,106 @@ impl Field {
 /// * `#[derivative(Debug(foo="bar")]` is represented as `("Debug", [("foo", Some("bar"))])`.
 struct MetaItem<'a>(&'a str, Vec<(&'a str, Option<&'a str>)>);
 
-/// Parse an arbitrary item for our limited `MetaItem` subset.
-fn read_items(item: &syn::MetaItem) -> Result<MetaItem, String> {
+/// Names the `#[derivative(...)]` meta item an error concerns, eg. `Debug`, `PartialEq`.
+///
+/// This is *not* a source span, and no real one is delivered anywhere in this file: `syn` 0.11's
+/// `MetaItem` carries no token position information, so there is nothing to point a spanned
+/// diagnostic at, let alone underline. This just carries the name of the item being parsed (the
+/// trait, not the specific sub-key) so error messages can say what they're about instead of
+/// going silent.
+#[derive(Clone)]
+struct ItemName(String);
+
+impl ItemName {
+    fn new(name: &str) -> Self {
+        ItemName(name.to_string())
+    }
+}
+
+impl fmt::Display for ItemName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`", self.0)
+    }
+}
+
+/// Accumulates every problem found while parsing `derivative` attributes instead of bailing out
+/// on the first one, so that a single compile surfaces all the mistakes in a derive at once.
+///
+/// This only delivers half of what spanned compile errors would: every mistake is reported
+/// instead of just the first, but `check` still collapses them into one plain `String` (joined
+/// by `\n`), not a list of individually-spanned diagnostics — there's no per-error underline at
+/// the offending tokens, because `ItemName` above has no token position to underline.
+struct Ctxt {
+    errors: RefCell<Vec<String>>,
+}
+
+impl Ctxt {
+    fn new() -> Self {
+        Ctxt { errors: RefCell::new(Vec::new()) }
+    }
+
+    /// Record `msg` against the meta item named by `name`.
+    fn error(&self, name: &ItemName, msg: String) {
+        self.errors.borrow_mut().push(format!("{}: {}", name, msg));
+    }
+
+    /// Turn every collected error into a single combined message, or `Ok(())` if none were
+    /// recorded. Still just one `String`: see the caveat on `Ctxt` above.
+    fn check(self) -> Result<(), String> {
+        let errors = self.errors.into_inner();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("\n"))
+        }
+    }
+}
+
+/// Get the name identifying a meta item, for use in error messages.
+fn item_name(item: &syn::MetaItem) -> &str {
+    match *item {
+        syn::MetaItem::Word(ref name) |
+        syn::MetaItem::List(ref name, _) |
+        syn::MetaItem::NameValue(ref name, _) => name.as_ref(),
+    }
+}
+
+/// Parse an arbitrary item for our limited `MetaItem` subset, recording any problems on `cx`
+/// instead of aborting so that the rest of the item can still be parsed.
+fn read_items<'a>(cx: &Ctxt, loc: &ItemName, item: &'a syn::MetaItem) -> Option<MetaItem<'a>> {
     match *item {
-        syn::MetaItem::Word(ref name) => Ok(MetaItem(name.as_ref(), Vec::new())),
+        syn::MetaItem::Word(ref name) => Some(MetaItem(name.as_ref(), Vec::new())),
         syn::MetaItem::List(ref name, ref values) => {
-            let values = try!(
-                values
+            let values = values
                 .iter()
-                .map(|value| {
-                    match *value {
-                        syn::MetaItem::Word(..) | syn::MetaItem::List(..) => {
-                            Err("Expected named value".to_string())
-                        }
-                        syn::MetaItem::NameValue(ref name, ref value) => {
-                            let value = try!(str_or_err(value));
-
-                            Ok((name.as_ref(), Some(value)))
+                .filter_map(|value| match *value {
+                    syn::MetaItem::Word(..) | syn::MetaItem::List(..) => {
+                        cx.error(loc, "expected named value".to_string());
+                        None
+                    }
+                    syn::MetaItem::NameValue(ref name, ref value) => {
+                        match str_or_err(value) {
+                            Ok(value) => Some((name.as_ref(), Some(value))),
+                            Err(e) => {
+                                cx.error(loc, e);
+                                None
+                            }
                         }
                     }
                 })
-                .collect()
-            );
+                .collect();
 
-            Ok(MetaItem(name.as_ref(), values))
+            Some(MetaItem(name.as_ref(), values))
         }
         syn::MetaItem::NameValue(ref name, ref value) => {
-            let value = try!(str_or_err(value));
-
-            Ok(MetaItem(name.as_ref(), vec![(value, None)]))
+            match str_or_err(value) {
+                Ok(value) => Some(MetaItem(name.as_ref(), vec![(value, None)])),
+                Err(e) => {
+                    cx.error(loc, e);
+                    None
+                }
+            }
         }
     }
 }
@@ -414,32 +755,131 @@ fn derivative_attribute(attr: &syn::Attribute) -> Option<&[syn::MetaItem]> {
 /// `"false"`. The `default` parameter specifies what the value of the boolean is when only its
 /// name is specified (eg. `Debug="ignore"` is equivalent to `Debug(ignore="true")`). The `name`
 /// parameter is used for error reporting.
-fn parse_boolean_meta_item(item: &Option<&str>, default: bool, name: &str) -> Result<bool, String> {
+fn parse_boolean_meta_item(cx: &Ctxt, loc: &ItemName, item: &Option<&str>, default: bool, name: &str) -> Option<bool> {
     match *item {
-        Some("true") => Ok(true),
-        Some("false") => Ok(false),
-        Some(_) => Err(format!("Invalid value for `{}`", name)),
-        None => Ok(default),
+        Some("true") => Some(true),
+        Some("false") => Some(false),
+        Some(_) => {
+            cx.error(loc, format!("invalid value for `{}`", name));
+            None
+        }
+        None => Some(default),
     }
 }
 
 /// Parse a `bound` item.
 fn parse_bound(
+    cx: &Ctxt,
+    loc: &ItemName,
     opt_bounds: &mut Option<Vec<syn::WherePredicate>>,
-    value: Option<&str>
-) -> Result<(), String> {
+    value: Option<&str>,
+) {
     let mut bounds = opt_bounds.take().unwrap_or_default();
-    let bound = try!(value.ok_or_else(|| "`bound` needs a value".to_string()));
+
+    let bound = match value {
+        Some(bound) => bound,
+        None => {
+            cx.error(loc, "`bound` needs a value".to_string());
+            *opt_bounds = Some(bounds);
+            return;
+        }
+    };
 
     if !bound.is_empty() {
-        let where_clause = syn::parse_where_clause(&format!("where {}", bound));
-        let mut predicates = try!(where_clause).predicates;
-        bounds.append(&mut predicates);
+        match syn::parse_where_clause(&format!("where {}", bound)) {
+            Ok(mut where_clause) => bounds.append(&mut where_clause.predicates),
+            Err(e) => cx.error(loc, e),
+        }
     }
 
     *opt_bounds = Some(bounds);
+}
 
-    Ok(())
+/// Parse a `path`-valued item (eg. `format_with`, `compare_with`, `hash_with`).
+fn parse_path_value(cx: &Ctxt, loc: &ItemName, value: Option<&str>, name: &str) -> Option<syn::Path> {
+    let value = match value {
+        Some(value) => value,
+        None => {
+            cx.error(loc, format!("`{}` needs a value", name));
+            return None;
+        }
+    };
+
+    match syn::parse_path(value) {
+        Ok(path) => Some(path),
+        Err(e) => {
+            cx.error(loc, e);
+            None
+        }
+    }
+}
+
+/// Parse a string-valued item (eg. `rename`).
+fn parse_string_value(cx: &Ctxt, loc: &ItemName, value: Option<&str>, name: &str) -> Option<String> {
+    match value {
+        Some(value) => Some(value.to_string()),
+        None => {
+            cx.error(loc, format!("`{}` needs a value", name));
+            None
+        }
+    }
+}
+
+/// Parse a `rename_all` item into the `RenameRule` it names.
+fn parse_rename_rule(cx: &Ctxt, loc: &ItemName, value: Option<&str>) -> Option<RenameRule> {
+    let value = match value {
+        Some(value) => value,
+        None => {
+            cx.error(loc, "`rename_all` needs a value".to_string());
+            return None;
+        }
+    };
+
+    match RenameRule::from_str(value) {
+        Ok(rule) => Some(rule),
+        Err(e) => {
+            cx.error(loc, e);
+            None
+        }
+    }
+}
+
+/// Parse a `type`-valued item (eg. `rhs`).
+fn parse_type_value(cx: &Ctxt, loc: &ItemName, value: Option<&str>, name: &str) -> Option<syn::Ty> {
+    let value = match value {
+        Some(value) => value,
+        None => {
+            cx.error(loc, format!("`{}` needs a value", name));
+            return None;
+        }
+    };
+
+    match syn::parse_type(value) {
+        Ok(ty) => Some(ty),
+        Err(e) => {
+            cx.error(loc, e);
+            None
+        }
+    }
+}
+
+/// Parse an `expr`-valued item (eg. `value`).
+fn parse_expr_value(cx: &Ctxt, loc: &ItemName, value: Option<&str>, name: &str) -> Option<syn::Expr> {
+    let value = match value {
+        Some(value) => value,
+        None => {
+            cx.error(loc, format!("`{}` needs a value", name));
+            return None;
+        }
+    };
+
+    match syn::parse_expr(value) {
+        Ok(expr) => Some(expr),
+        Err(e) => {
+            cx.error(loc, e);
+            None
+        }
+    }
 }
 
 /// Get the string out of a string literal or report an error for other literals.
@@ -450,3 +890,106 @@ fn str_or_err(lit: &syn::Lit) -> Result<&str, String> {
         Err("Expected string".to_string())
     }
 }
+
+/// The casing convention used to rename fields and variants in the generated `Debug` output, set
+/// via `#[derivative(Debug(rename_all = "..."))]` and overridable per field with
+/// `#[derivative(Debug(rename = "..."))]`.
+#[derive(Debug, Clone, Copy)]
+pub enum RenameRule {
+    LowerCase,
+    UpperCase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    fn from_str(rule: &str) -> Result<Self, String> {
+        match rule {
+            "lowercase" => Ok(RenameRule::LowerCase),
+            "UPPERCASE" => Ok(RenameRule::UpperCase),
+            "PascalCase" => Ok(RenameRule::PascalCase),
+            "camelCase" => Ok(RenameRule::CamelCase),
+            "snake_case" => Ok(RenameRule::SnakeCase),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnakeCase),
+            "kebab-case" => Ok(RenameRule::KebabCase),
+            "SCREAMING-KEBAB-CASE" => Ok(RenameRule::ScreamingKebabCase),
+            _ => Err(format!("unknown `rename_all` rule `{}`", rule)),
+        }
+    }
+
+    /// Apply this rule to a field identifier (eg. `foo_bar`), splitting on `_`.
+    pub fn apply_to_field(&self, field: &str) -> String {
+        let words: Vec<&str> = field.split('_').filter(|word| !word.is_empty()).collect();
+        join_words(*self, &words)
+    }
+
+    /// Apply this rule to a variant identifier (eg. `FooBar`), splitting on case boundaries.
+    pub fn apply_to_variant(&self, variant: &str) -> String {
+        let words = split_on_case_boundaries(variant);
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        join_words(*self, &words)
+    }
+}
+
+/// Join `words` according to `rule`. Each rule picks its own separator (none, `_`, or `-`), so
+/// there's nothing left for a caller to configure.
+fn join_words(rule: RenameRule, words: &[&str]) -> String {
+    match rule {
+        RenameRule::LowerCase => words.join("").to_lowercase(),
+        RenameRule::UpperCase => words.join("").to_uppercase(),
+        RenameRule::PascalCase => words.iter().map(|word| capitalize(word)).collect(),
+        RenameRule::CamelCase => {
+            words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| if i == 0 { word.to_lowercase() } else { capitalize(word) })
+                .collect()
+        }
+        RenameRule::SnakeCase => {
+            words.iter().map(|word| word.to_lowercase()).collect::<Vec<_>>().join("_")
+        }
+        RenameRule::ScreamingSnakeCase => {
+            words.iter().map(|word| word.to_uppercase()).collect::<Vec<_>>().join("_")
+        }
+        RenameRule::KebabCase => {
+            words.iter().map(|word| word.to_lowercase()).collect::<Vec<_>>().join("-")
+        }
+        RenameRule::ScreamingKebabCase => {
+            words.iter().map(|word| word.to_uppercase()).collect::<Vec<_>>().join("-")
+        }
+    }
+}
+
+/// Capitalize the first character of `word`, lower-casing the rest.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase(),
+        None => String::new(),
+    }
+}
+
+/// Split an identifier like `FooBar` into `["Foo", "Bar"]` on upper-case boundaries.
+fn split_on_case_boundaries(ident: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for ch in ident.chars() {
+        if ch.is_uppercase() && !current.is_empty() {
+            words.push(current);
+            current = String::new();
+        }
+        current.push(ch);
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}